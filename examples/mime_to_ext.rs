@@ -4,6 +4,7 @@
 //! # Usage (from source)
 //! ```bash
 //! cargo run --features std --example mime_to_ext -- <mime-type|extension>
+//! cargo run --features std --example mime_to_ext -- scan <dir> [--ignore-unknown-exts] [--fix-script]
 //! ```
 //!
 //! # Examples (without installation)
@@ -18,6 +19,9 @@
 //! mp3, mp1, mp2
 //! $ cargo run --features std --example mime_to_ext -- mp1
 //! audio/mpeg
+//! $ cargo run --features std --example mime_to_ext -- scan ./downloads
+//! ./downloads/invoice.png: detected application/pdf (.pdf), current extension is .png
+//! $ cargo run --features std --example mime_to_ext -- scan ./downloads --fix-script > fix.sh
 //! ```
 //!
 //! # Install locally (makes `mime_to_ext` available everywhere)
@@ -40,23 +44,32 @@
 //! ```
 //!
 //! # Exit status
-//! * 0  – successful lookup  
-//! * 1  – missing / invalid argument  
+//! * 0  – successful lookup
+//! * 1  – missing / invalid argument
 //! * 2  – unknown MIME or extension (prints `?`)
 
+use mime_to_ext::scan::{self, ScanOptions};
 use mime_to_ext::{ext_to_mime, mime_to_ext};
+use std::path::Path;
 use std::{env, process};
 
 /// Entry point for the mime_to_ext.
 fn main() {
-    let arg = match env::args().nth(1) {
+    let mut args = env::args().skip(1);
+    let arg = match args.next() {
         Some(a) => a,
         None => {
             eprintln!("usage: mime_to_ext <mime-type|extension>");
+            eprintln!("       mime_to_ext scan <dir> [--ignore-unknown-exts] [--fix-script]");
             process::exit(1);
         }
     };
 
+    if arg == "scan" {
+        run_scan(args);
+        return;
+    }
+
     let out = if arg.contains('/') {
         match mime_to_ext(&arg) {
             Some(exts) => exts.join(", "),
@@ -67,3 +80,42 @@ fn main() {
     };
     println!("{}", out);
 }
+
+/// Walk a directory, reporting files whose extension doesn't match their
+/// sniffed content.
+fn run_scan(mut args: impl Iterator<Item = String>) {
+    let dir = match args.next() {
+        Some(d) => d,
+        None => {
+            eprintln!("usage: mime_to_ext scan <dir> [--ignore-unknown-exts] [--fix-script]");
+            process::exit(1);
+        }
+    };
+
+    let mut options = ScanOptions::default();
+    let mut fix_script = false;
+    for flag in args {
+        match flag.as_str() {
+            "--ignore-unknown-exts" => options.ignore_unknown_exts = true,
+            "--fix-script" => fix_script = true,
+            other => {
+                eprintln!("unknown flag: {}", other);
+                process::exit(1);
+            }
+        }
+    }
+
+    let findings = match scan::scan(Path::new(&dir), options) {
+        Ok(findings) => findings,
+        Err(e) => {
+            eprintln!("scan failed: {}", e);
+            process::exit(1);
+        }
+    };
+
+    if fix_script {
+        print!("{}", scan::fix_script(&findings));
+    } else {
+        print!("{}", scan::report(&findings));
+    }
+}
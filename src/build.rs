@@ -1,15 +1,89 @@
-//! Build-time validation for the embedded MIME database.
+//! Build-time code generation for the embedded MIME database.
 //!
 //! This build script is executed automatically by Cargo before the crate is
-//! compiled.  It guarantees that `data/mime_db.json` is syntactically valid
-//! JSON and contains the expected structure (`Map<String, Vec<String>>`).
-//! If the file is malformed the build fails immediately, preventing a broken
-//! database from being shipped.
+//! compiled. It reads `data/mime_db.json`, validates that it has the
+//! expected structure (`Map<String, Vec<String>>`), and emits two
+//! FNV-1a-hashed bucket tables — forward (MIME → extensions) and inverse
+//! (extension → MIME) — into `$OUT_DIR/mime_tables.rs`, which `src/cache.rs`
+//! `include!`s. If the file is malformed the build fails immediately,
+//! preventing a broken database from being shipped.
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+// Build scripts can't depend on the crate they're building, but plain
+// `include!` works: this pulls in the exact same `fnv1a_hash`/`bucket_index`
+// (and `BUCKET_COUNT`) used at runtime by `src/cache.rs`, so bucketing can
+// never drift between build time and run time.
+include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/cache/fnv.rs"));
+
 fn main() {
     println!("cargo:rerun-if-changed=data/mime_db.json");
     let json = include_str!("data/mime_db.json");
-    if let Err(e) = serde_json::from_str::<serde_json::Map<String, Vec<String>>>(json) {
-        eprintln!("data/mime_db.json is not valid JSON: {}", e);
-        std::process::exit(1);
+    let db: BTreeMap<String, Vec<String>> = match serde_json::from_str(json) {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("data/mime_db.json is not valid JSON: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let mut forward: Vec<Vec<(String, Vec<String>)>> = vec![Vec::new(); BUCKET_COUNT];
+    // The first MIME type seen for a given extension wins. `db` is a
+    // BTreeMap, so that is the alphabetically first MIME type listing the
+    // extension, deterministic regardless of JSON key order.
+    let mut first_mime_for_ext: BTreeMap<String, String> = BTreeMap::new();
+
+    for (mime, exts) in &db {
+        for ext in exts {
+            first_mime_for_ext
+                .entry(ext.clone())
+                .or_insert_with(|| mime.clone());
+        }
+
+        forward[bucket_index(fnv1a_hash(mime.as_bytes()))].push((mime.clone(), exts.clone()));
     }
-}
\ No newline at end of file
+
+    let mut inverse: Vec<Vec<(String, String)>> = vec![Vec::new(); BUCKET_COUNT];
+    for (ext, mime) in &first_mime_for_ext {
+        inverse[bucket_index(fnv1a_hash(ext.as_bytes()))].push((ext.clone(), mime.clone()));
+    }
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "pub(crate) static FORWARD_BUCKETS: [&[(&str, &[&str])]; {BUCKET_COUNT}] = ["
+    )
+    .unwrap();
+    for bucket in &forward {
+        write!(out, "    &[").unwrap();
+        for (mime, exts) in bucket {
+            write!(out, "({mime:?}, &[").unwrap();
+            for ext in exts {
+                write!(out, "{ext:?}, ").unwrap();
+            }
+            write!(out, "]), ").unwrap();
+        }
+        writeln!(out, "],").unwrap();
+    }
+    writeln!(out, "];").unwrap();
+
+    writeln!(
+        out,
+        "pub(crate) static INVERSE_BUCKETS: [&[(&str, &str)]; {BUCKET_COUNT}] = ["
+    )
+    .unwrap();
+    for bucket in &inverse {
+        write!(out, "    &[").unwrap();
+        for (ext, mime) in bucket {
+            write!(out, "({ext:?}, {mime:?}), ").unwrap();
+        }
+        writeln!(out, "],").unwrap();
+    }
+    writeln!(out, "];").unwrap();
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("mime_tables.rs"), out).unwrap();
+}
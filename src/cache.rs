@@ -1,25 +1,32 @@
-//! One-time load, zero-copy lookup tables for MIME ↔ extension mappings.
+//! Compile-time, zero-allocation lookup tables for MIME ↔ extension mappings.
 //!
-//! The embedded JSON file (`/data/mime_db.json`) is parsed **once** on first
-//! access and stored in a `HashMap`.  
-//! Everything is `no_std` + `alloc` only; no further heap allocations occur
-//! after the initial parse.
+//! `build.rs` reads `data/mime_db.json` once, at build time, and emits two
+//! FNV-1a-hashed bucket tables (`FORWARD_BUCKETS`, `INVERSE_BUCKETS`) into
+//! `OUT_DIR`. This module just hashes the lookup key, masks it to a bucket
+//! index, and linear-scans that bucket for an exact string match — no
+//! heap allocation and no runtime parsing after the binary is built.
 //!
 //! Public API is exposed through the root `lib.rs`; this module is an
 //! implementation detail.
-use alloc::string::String;
-use alloc::vec::Vec;
-use hashbrown::HashMap;
-use once_cell::sync::Lazy;
-extern crate alloc;
+mod fnv;
 
-/// Internal type: maps a MIME type to its associated extensions.
-type JsonDb = HashMap<String, Vec<String>>;
+include!(concat!(env!("OUT_DIR"), "/mime_tables.rs"));
 
-/// Raw JSON bytes compiled into the binary.
-static JSON_SOURCE: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/data/mime_db.json"));
+/// Look up the extensions registered for a MIME type.
+pub(crate) fn lookup_mime(mime: &str) -> Option<&'static [&'static str]> {
+    let bucket = &FORWARD_BUCKETS[fnv::bucket_index(fnv::fnv1a_hash(mime.as_bytes()))];
+    bucket
+        .iter()
+        .find(|&&(key, _)| key == mime)
+        .map(|&(_, exts)| exts)
+        .filter(|exts| !exts.is_empty())
+}
 
-/// Lazily-loaded, globally-shared database.  
-/// First access parses the JSON; every later call re-uses the same `JsonDb`.
-pub static DB: Lazy<Result<JsonDb, serde_json::Error>> =
-    Lazy::new(|| serde_json::from_str(JSON_SOURCE));
+/// Look up the canonical MIME type registered for an extension.
+pub(crate) fn lookup_ext(ext: &str) -> Option<&'static str> {
+    let bucket = &INVERSE_BUCKETS[fnv::bucket_index(fnv::fnv1a_hash(ext.as_bytes()))];
+    bucket
+        .iter()
+        .find(|&&(key, _)| key == ext)
+        .map(|&(_, mime)| mime)
+}
@@ -0,0 +1,34 @@
+// FNV-1a hashing for the generated lookup tables in the parent module.
+//
+// Kept tiny and `const fn`-only so it has no bearing on `no_std`
+// compatibility. `build.rs` `include!`s this same file to bucket entries
+// the same way at code-generation time, so the two can never drift apart.
+//
+// Plain `//` comments only: this file is spliced into the middle of
+// build.rs via `include!`, where an inner doc comment (`//!`) would not be
+// the first thing in its enclosing scope and so would fail to compile
+// (E0753). As a real module (`mod fnv;` from cache.rs) plain comments are
+// equally legal, just without the rustdoc rendering.
+
+const FNV_OFFSET_BASIS: u32 = 0x811c_9dc5;
+const FNV_PRIME: u32 = 0x0100_0193;
+
+/// Number of hash buckets in the generated tables.
+pub(crate) const BUCKET_COUNT: usize = 256;
+
+/// Hash `bytes` with the 32-bit FNV-1a algorithm.
+pub(crate) const fn fnv1a_hash(bytes: &[u8]) -> u32 {
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u32;
+        hash = hash.wrapping_mul(FNV_PRIME);
+        i += 1;
+    }
+    hash
+}
+
+/// Map a hash to a bucket index.
+pub(crate) const fn bucket_index(hash: u32) -> usize {
+    (hash as usize) & (BUCKET_COUNT - 1)
+}
@@ -1,21 +1,45 @@
 #![no_std]
-//! no_std MIME ↔ extension lookup from embedded JSON, zero OS dependencies.
+//! no_std MIME ↔ extension lookup from a compile-time generated table, zero OS dependencies.
 //!
-//! The database is embedded once at compile time and lazily parsed on first
-//! use. All returned strings are `'static` and live for the entire program
-//! duration.
+//! `build.rs` reads the embedded JSON once, at build time, and bakes two
+//! FNV-1a-hashed bucket tables into the binary (see [`cache`]). A lookup
+//! just hashes the key and linear-scans the matching bucket: no heap
+//! allocation, no runtime JSON parsing. The one exception is
+//! [`mime_to_ext`]'s retry for a key with parameters, which parses it into
+//! a [`media_type::MediaType`] and so allocates. All returned strings are `'static`
+//! and live for the entire program duration.
 mod cache;
-use ahash::AHasher;
-use core::hash::BuildHasherDefault;
-use hashbrown::HashMap;
-use once_cell::sync::Lazy;
+pub mod media_type;
+#[cfg(feature = "std")]
+pub mod overlay;
+#[cfg(feature = "std")]
+pub mod scan;
+pub mod sniff;
+
+/// Look up a MIME type's extensions, consulting the `std`-only overlay
+/// (see [`overlay`]) ahead of the compiled-in table.
+fn lookup_mime_any(mime: &str) -> Option<&'static [&'static str]> {
+    #[cfg(feature = "std")]
+    {
+        if let Some(exts) = overlay::lookup_mime(mime) {
+            return Some(exts);
+        }
+    }
+    cache::lookup_mime(mime)
+}
 
 /// Return the file extensions (without leading dot) for a MIME type.
 ///
+/// `mime` may be a bare `type/subtype` or a full `Content-Type`-style
+/// string with parameters (e.g. `text/plain; charset=utf-8`): if the raw
+/// string isn't a key in the database, it is parsed as a [`media_type`]
+/// and retried using just its [`MediaType::essence`](media_type::MediaType::essence).
+///
+/// Under the `std` feature, entries loaded into the [`overlay`] (e.g. from
+/// `/etc/mime.types`) take priority over the compiled-in database.
+///
 /// Returns `None` if
-/// - the MIME type is unknown,
-/// - the embedded JSON database failed to parse (i.e. the crate was compiled
-///   with broken data), or
+/// - the MIME type is unknown (under either form), or
 /// - the entry exists but contains no extensions.
 ///
 /// # Example
@@ -24,18 +48,22 @@ use once_cell::sync::Lazy;
 /// assert_eq!(mime_to_ext("image/png"), Some(&["png"][..]));
 /// assert_eq!(mime_to_ext("foo/bar"), None);
 /// assert_eq!(mime_to_ext("audio/mpeg"), Some(&["mp3", "mp1", "mp2"][..]));
+/// assert_eq!(mime_to_ext("image/png; charset=binary"), Some(&["png"][..]));
 /// ``````
 pub fn mime_to_ext(mime: &str) -> Option<&'static [&'static str]> {
-    match cache::DB.as_ref() {
-        Some(db) => db.get(mime).map(|v| v.as_slice()),
-        None => None,
+    if let Some(exts) = lookup_mime_any(mime) {
+        return Some(exts);
     }
+    let essence = media_type::MediaType::parse(mime)?.essence();
+    lookup_mime_any(essence.as_str())
 }
+
 /// Return the canonical MIME type for a file extension.
 ///
-/// `None` is returned when
-/// - the extension is unknown, or
-/// - the JSON database failed to parse.
+/// Under the `std` feature, entries loaded into the [`overlay`] (e.g. from
+/// `/etc/mime.types`) take priority over the compiled-in database.
+///
+/// `None` is returned when the extension is unknown.
 ///
 /// # Example
 /// ```
@@ -43,43 +71,20 @@ pub fn mime_to_ext(mime: &str) -> Option<&'static [&'static str]> {
 /// assert_eq!(ext_to_mime("png"), Some("image/png"));
 /// assert_eq!(ext_to_mime("QQQ"), None);
 /// ```
-/// Inverted map built once at first call; speed > allocations.
-#[allow(clippy::type_complexity)]
 pub fn ext_to_mime(ext: &str) -> Option<&'static str> {
-    static INV: Lazy<Option<HashMap<&'static str, &'static str, BuildHasherDefault<AHasher>>>> =
-        Lazy::new(|| match cache::DB.as_ref() {
-            Some(db) => {
-                let mut map = HashMap::with_hasher(BuildHasherDefault::<AHasher>::default());
-                for (&mime, exts) in db.iter() {
-                    for &e in exts {
-                        map.entry(e).or_insert(mime);
-                    }
-                }
-                Some(map)
-            }
-            None => None,
-        });
-
-    match INV.as_ref() {
-        Some(map) => map.get(ext).copied(),
-        None => None,
+    #[cfg(feature = "std")]
+    {
+        if let Some(mime) = overlay::lookup_ext(ext) {
+            return Some(mime);
+        }
     }
+    cache::lookup_ext(ext)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    /// Tests that the embedded JSON database loads successfully.
-    /// If the JSON is malformed this test fails, preventing `cargo test` from passing.
-    #[test]
-    fn db_loads_successfully() {
-        assert!(
-            cache::DB.is_some(),
-            "embedded JSON database failed to parse"
-        );
-    }
-
     /// Unit-test: MIME type that maps to several extensions.
     ///
     /// `audio/mpeg` is registered for more than one extension; the function
@@ -112,4 +117,15 @@ mod tests {
         assert_eq!(mime_to_ext("foo/bar"), None);
         assert_eq!(ext_to_mime("qqq"), None);
     }
+
+    /// `mime_to_ext` accepts a full `Content-Type`-style string, not just a
+    /// bare `type/subtype` key.
+    #[test]
+    fn mime_to_ext_accepts_full_content_type() {
+        assert_eq!(
+            mime_to_ext("image/png; charset=binary"),
+            Some(&["png"][..])
+        );
+        assert_eq!(mime_to_ext(r#"audio/mpeg; quality="high""#), Some(&["mp3", "mp1", "mp2"][..]));
+    }
 }
@@ -0,0 +1,151 @@
+//! A small RFC 2045 §5.1 media-type parser.
+//!
+//! This is not a full implementation of the grammar (no comments, no
+//! header folding) but covers what real `Content-Type` headers use in
+//! practice: `type "/" subtype` followed by zero or more
+//! `; attribute=value` parameters, where `value` is a bare token or a
+//! `"quoted-string"`. Backslash escapes inside a quoted-string are not
+//! decoded: the first `"` after the opening one ends the value.
+extern crate alloc;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A parsed media type, e.g. `text/plain; charset=utf-8`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaType {
+    pub type_: String,
+    pub subtype: String,
+    pub params: Vec<(String, String)>,
+}
+
+impl MediaType {
+    /// Parse a media type from a raw `Content-Type`-style string.
+    ///
+    /// Returns `None` if the string has no `type/subtype` pair, contains a
+    /// control character, or has an unterminated quoted-string value.
+    ///
+    /// # Example
+    /// ```
+    /// # use mime_to_ext::media_type::MediaType;
+    /// let mt = MediaType::parse("Text/Plain; charset=\"UTF-8\"").unwrap();
+    /// assert_eq!(mt.essence(), "text/plain");
+    /// assert_eq!(mt.params, vec![("charset".into(), "UTF-8".into())]);
+    /// ```
+    pub fn parse(s: &str) -> Option<MediaType> {
+        if s.bytes().any(|b| b.is_ascii_control() && b != b'\t') {
+            return None;
+        }
+
+        let (essence, mut rest) = match s.split_once(';') {
+            Some((e, r)) => (e, r),
+            None => (s, ""),
+        };
+        let (type_, subtype) = essence.trim().split_once('/')?;
+        let type_ = type_.trim();
+        let subtype = subtype.trim();
+        if type_.is_empty() || subtype.is_empty() {
+            return None;
+        }
+
+        let mut params = Vec::new();
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() {
+                break;
+            }
+            let (attr, after_attr) = rest.split_once('=')?;
+            let attr = attr.trim();
+            let after_attr = after_attr.trim_start();
+
+            let (value, remainder) = if let Some(quoted) = after_attr.strip_prefix('"') {
+                let end = quoted.find('"')?;
+                (&quoted[..end], &quoted[end + 1..])
+            } else {
+                // Locate but don't consume the separating `;`: the end of
+                // the loop body below strips it, same as it does for the
+                // quoted-string branch above.
+                match after_attr.find(';') {
+                    Some(i) => (after_attr[..i].trim(), &after_attr[i..]),
+                    None => (after_attr.trim(), ""),
+                }
+            };
+
+            params.push((String::from(attr), String::from(value)));
+
+            rest = remainder.trim_start();
+            if rest.is_empty() {
+                break;
+            }
+            rest = rest.strip_prefix(';')?;
+        }
+
+        Some(MediaType {
+            type_: type_.to_ascii_lowercase(),
+            subtype: subtype.to_ascii_lowercase(),
+            params,
+        })
+    }
+
+    /// The bare `type/subtype` with parameters stripped.
+    pub fn essence(&self) -> String {
+        alloc::format!("{}/{}", self.type_, self.subtype)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bare_essence() {
+        let mt = MediaType::parse("image/png").unwrap();
+        assert_eq!(mt.essence(), "image/png");
+        assert!(mt.params.is_empty());
+    }
+
+    #[test]
+    fn lowercases_type_and_subtype() {
+        let mt = MediaType::parse("Text/PLAIN").unwrap();
+        assert_eq!(mt.type_, "text");
+        assert_eq!(mt.subtype, "plain");
+    }
+
+    #[test]
+    fn parses_bare_token_param() {
+        let mt = MediaType::parse("text/plain; charset=utf-8").unwrap();
+        assert_eq!(mt.params, alloc::vec![(String::from("charset"), String::from("utf-8"))]);
+    }
+
+    #[test]
+    fn parses_quoted_param() {
+        let mt = MediaType::parse(r#"text/plain; charset="UTF-8""#).unwrap();
+        assert_eq!(mt.params, alloc::vec![(String::from("charset"), String::from("UTF-8"))]);
+    }
+
+    #[test]
+    fn parses_multiple_params() {
+        let mt = MediaType::parse(r#"multipart/form-data; boundary=abc; charset=utf-8"#).unwrap();
+        assert_eq!(
+            mt.params,
+            alloc::vec![
+                (String::from("boundary"), String::from("abc")),
+                (String::from("charset"), String::from("utf-8")),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_missing_slash() {
+        assert_eq!(MediaType::parse("not-a-media-type"), None);
+    }
+
+    #[test]
+    fn rejects_control_characters() {
+        assert_eq!(MediaType::parse("text/plain\r\ninjected: 1"), None);
+    }
+
+    #[test]
+    fn rejects_unterminated_quoted_string() {
+        assert_eq!(MediaType::parse(r#"text/plain; charset="utf-8"#), None);
+    }
+}
@@ -0,0 +1,165 @@
+//! Runtime overlay augmenting the embedded database from OS-installed
+//! `mime.types` files, so callers can pick up locally installed type
+//! mappings without rebuilding the crate.
+//!
+//! Only available under the `std` feature: it needs filesystem access,
+//! which the `no_std` default build does not have.
+//!
+//! Since [`crate::mime_to_ext`] and [`crate::ext_to_mime`] are contractually
+//! `'static`, strings loaded here are leaked into the binary's lifetime via
+//! [`Box::leak`]. This is fine for an overlay loaded a handful of times at
+//! startup; it is not meant for repeatedly reloading large files.
+#![cfg(feature = "std")]
+extern crate std;
+
+use std::boxed::Box;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::string::{String, ToString};
+use std::sync::{OnceLock, RwLock};
+use std::vec::Vec;
+
+struct Overlay {
+    forward: HashMap<String, &'static [&'static str]>,
+    inverse: HashMap<String, &'static str>,
+}
+
+fn overlay() -> &'static RwLock<Overlay> {
+    static OVERLAY: OnceLock<RwLock<Overlay>> = OnceLock::new();
+    OVERLAY.get_or_init(|| {
+        RwLock::new(Overlay {
+            forward: HashMap::new(),
+            inverse: HashMap::new(),
+        })
+    })
+}
+
+fn leak_str(s: &str) -> &'static str {
+    Box::leak(s.to_string().into_boxed_str())
+}
+
+/// Parse a classic `mime.types` file — one record per line, a MIME type
+/// followed by whitespace-separated extensions — and merge it into the
+/// overlay consulted by [`crate::mime_to_ext`] and [`crate::ext_to_mime`]
+/// before they fall back to the compiled-in database.
+///
+/// Blank lines and `#` comments are ignored. An extension already present
+/// in the overlay keeps the MIME type it was first registered with.
+pub fn load_mime_types(path: &Path) -> io::Result<()> {
+    let contents = fs::read_to_string(path)?;
+    let mut guard = overlay().write().unwrap();
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = line.split_whitespace();
+        let mime = match fields.next() {
+            Some(m) => m,
+            None => continue,
+        };
+        let new_exts: Vec<&str> = fields.collect();
+        if new_exts.is_empty() {
+            continue;
+        }
+
+        for &ext in &new_exts {
+            guard
+                .inverse
+                .entry(ext.to_string())
+                .or_insert_with(|| leak_str(mime));
+        }
+
+        let mut merged: Vec<&'static str> = guard
+            .forward
+            .get(mime)
+            .map(|exts| exts.to_vec())
+            .unwrap_or_default();
+        merged.extend(new_exts.into_iter().map(leak_str));
+        guard
+            .forward
+            .insert(mime.to_string(), Box::leak(merged.into_boxed_slice()));
+    }
+
+    Ok(())
+}
+
+/// Load the two conventional system locations, `/etc/mime.types` and
+/// `~/.mime.types`, in that order. A missing file is silently skipped;
+/// any other I/O error (e.g. a permissions problem) is propagated.
+pub fn load_system_mime_types() -> io::Result<()> {
+    for path in system_mime_types_paths() {
+        match load_mime_types(&path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+fn system_mime_types_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::from([PathBuf::from("/etc/mime.types")]);
+    if let Some(home) = std::env::var_os("HOME") {
+        paths.push(PathBuf::from(home).join(".mime.types"));
+    }
+    paths
+}
+
+/// Look up the extensions registered for a MIME type in the overlay only.
+pub(crate) fn lookup_mime(mime: &str) -> Option<&'static [&'static str]> {
+    overlay().read().unwrap().forward.get(mime).copied()
+}
+
+/// Look up the canonical MIME type registered for an extension in the
+/// overlay only.
+pub(crate) fn lookup_ext(ext: &str) -> Option<&'static str> {
+    overlay().read().unwrap().inverse.get(ext).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn parses_classic_mime_types_format() {
+        let mut file = std::env::temp_dir();
+        file.push("mime_to_ext_overlay_test.types");
+        {
+            let mut f = fs::File::create(&file).unwrap();
+            writeln!(f, "# a comment").unwrap();
+            writeln!(f).unwrap();
+            writeln!(f, "application/vnd.acme.widget  widget wdg").unwrap();
+        }
+
+        load_mime_types(&file).unwrap();
+        assert_eq!(
+            lookup_mime("application/vnd.acme.widget"),
+            Some(&["widget", "wdg"][..])
+        );
+        assert_eq!(lookup_ext("widget"), Some("application/vnd.acme.widget"));
+
+        fs::remove_file(&file).ok();
+    }
+
+    #[test]
+    fn missing_mime_types_file_reports_not_found() {
+        // `load_system_mime_types` itself isn't exercised here: it loads
+        // from real system paths (`/etc/mime.types`, `~/.mime.types`) into
+        // the crate-wide overlay singleton, which would merge whatever
+        // happens to be installed on the test host into the process-global
+        // state shared by every other test in this binary. Instead, check
+        // that `load_mime_types` reports a missing file as `NotFound`, the
+        // one error kind `load_system_mime_types` skips.
+        let mut file = std::env::temp_dir();
+        file.push("mime_to_ext_overlay_test_missing.types");
+        fs::remove_file(&file).ok();
+
+        assert_eq!(load_mime_types(&file).unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,273 @@
+//! Directory-scan subsystem for detecting (and generating fixes for) files
+//! whose extension doesn't match their actual content.
+//!
+//! Walks a directory tree, sniffs each file's real MIME type (see
+//! [`crate::sniff`]), and compares it to the type implied by the file's
+//! current extension (see [`crate::ext_to_mime`]). Only available under
+//! the `std` feature, since scanning is inherently filesystem work.
+#![cfg(feature = "std")]
+extern crate std;
+
+use crate::{ext_to_mime, mime_to_ext, sniff};
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+/// A file whose current extension doesn't match its sniffed content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub path: PathBuf,
+    pub detected_mime: &'static str,
+    pub current_ext: String,
+    pub suggested_ext: &'static str,
+}
+
+/// Options controlling a directory scan.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScanOptions {
+    /// Suppress findings for files whose current extension isn't in the
+    /// database at all, so obscure-but-legitimate formats aren't flagged
+    /// just because they're not covered yet.
+    pub ignore_unknown_exts: bool,
+}
+
+/// Recursively walk `dir`, returning a [`Finding`] for every file whose
+/// sniffed content doesn't match what its current extension implies.
+///
+/// Only a problem reading `dir` itself (e.g. it doesn't exist) is
+/// propagated as an error; unreadable subdirectories or files encountered
+/// deeper in the tree (permission-denied, vanished mid-scan, ...) are
+/// skipped so one bad entry doesn't discard findings already collected.
+pub fn scan(dir: &Path, options: ScanOptions) -> io::Result<Vec<Finding>> {
+    let entries = fs::read_dir(dir)?;
+    let mut findings = Vec::new();
+    walk_entries(entries, options, &mut findings);
+    Ok(findings)
+}
+
+fn walk(dir: &Path, options: ScanOptions, findings: &mut Vec<Finding>) {
+    if let Ok(entries) = fs::read_dir(dir) {
+        walk_entries(entries, options, findings);
+    }
+}
+
+fn walk_entries(entries: fs::ReadDir, options: ScanOptions, findings: &mut Vec<Finding>) {
+    for entry in entries {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(_) => continue,
+        };
+        let path = entry.path();
+        let file_type = match entry.file_type() {
+            Ok(ft) => ft,
+            Err(_) => continue,
+        };
+        if file_type.is_dir() {
+            walk(&path, options, findings);
+        } else if file_type.is_file() {
+            if let Ok(Some(finding)) = check_file(&path, options) {
+                findings.push(finding);
+            }
+        }
+    }
+}
+
+fn check_file(path: &Path, options: ScanOptions) -> io::Result<Option<Finding>> {
+    let current_ext = match path.extension().and_then(|e| e.to_str()) {
+        Some(e) => e,
+        None => return Ok(None),
+    };
+    // Extensions are case-insensitive on every platform this crate targets;
+    // the database is keyed in lowercase, so normalize before comparing.
+    let current_ext_lower = current_ext.to_ascii_lowercase();
+
+    let current_mime = ext_to_mime(&current_ext_lower);
+    if current_mime.is_none() && options.ignore_unknown_exts {
+        return Ok(None);
+    }
+
+    let detected_mime = match sniff::detect_from_path(path)? {
+        Some(m) => m,
+        None => return Ok(None),
+    };
+
+    if current_mime == Some(detected_mime) {
+        return Ok(None);
+    }
+
+    let suggested_ext = match mime_to_ext(detected_mime).and_then(|exts| exts.first().copied()) {
+        Some(ext) => ext,
+        None => return Ok(None),
+    };
+
+    if suggested_ext == current_ext_lower {
+        return Ok(None);
+    }
+
+    Ok(Some(Finding {
+        path: path.to_path_buf(),
+        detected_mime,
+        current_ext: current_ext.to_string(),
+        suggested_ext,
+    }))
+}
+
+/// Render findings as a human-readable report, one line per mismatch.
+pub fn report(findings: &[Finding]) -> String {
+    let mut out = String::new();
+    for f in findings {
+        writeln!(
+            out,
+            "{}: detected {} (.{}), current extension is .{}",
+            f.path.display(),
+            f.detected_mime,
+            f.suggested_ext,
+            f.current_ext
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// Render findings as a shell script of `mv` commands, for the user to
+/// review and run themselves. Nothing in this crate executes these moves.
+///
+/// Each `mv` passes `-n` (no-clobber) so a suggested rename can never
+/// silently overwrite a file that already exists at the destination path;
+/// `mv` just declines that one move and the script continues.
+pub fn fix_script(findings: &[Finding]) -> String {
+    let mut out = String::from("#!/bin/sh\nset -e\n");
+    for f in findings {
+        let new_path = f.path.with_extension(f.suggested_ext);
+        writeln!(
+            out,
+            "mv -n -- {} {}",
+            shell_quote(&f.path.display().to_string()),
+            shell_quote(&new_path.display().to_string())
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// POSIX single-quote a string for safe inclusion in a shell command.
+/// Debug-formatting (`{:?}`) is not safe here: it doesn't escape `$`,
+/// backticks, or other shell metacharacters, which would let a crafted
+/// filename run arbitrary commands when the generated script is executed.
+fn shell_quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('\'');
+    for c in s.chars() {
+        if c == '\'' {
+            quoted.push_str("'\\''");
+        } else {
+            quoted.push(c);
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let mut dir = std::env::temp_dir();
+        dir.push(name);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn flags_a_mismatched_extension() {
+        let dir = temp_dir("mime_to_ext_scan_test_mismatch");
+        let file = dir.join("photo.txt");
+        fs::File::create(&file)
+            .unwrap()
+            .write_all(&[0xFF, 0xD8, 0xFF, 0x00])
+            .unwrap();
+
+        let findings = scan(&dir, ScanOptions::default()).unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].detected_mime, "image/jpeg");
+        assert_eq!(findings[0].current_ext, "txt");
+        assert_eq!(findings[0].suggested_ext, "jpg");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn matching_extension_is_not_a_finding() {
+        let dir = temp_dir("mime_to_ext_scan_test_match");
+        let file = dir.join("photo.jpg");
+        fs::File::create(&file)
+            .unwrap()
+            .write_all(&[0xFF, 0xD8, 0xFF, 0x00])
+            .unwrap();
+
+        let findings = scan(&dir, ScanOptions::default()).unwrap();
+        assert!(findings.is_empty());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn matching_extension_is_not_a_finding_regardless_of_case() {
+        let dir = temp_dir("mime_to_ext_scan_test_match_uppercase");
+        let file = dir.join("photo.JPG");
+        fs::File::create(&file)
+            .unwrap()
+            .write_all(&[0xFF, 0xD8, 0xFF, 0x00])
+            .unwrap();
+
+        let findings = scan(&dir, ScanOptions::default()).unwrap();
+        assert!(findings.is_empty());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn fix_script_quotes_shell_metacharacters_safely() {
+        let finding = Finding {
+            path: PathBuf::from("$(touch /tmp/mime_to_ext_pwned).txt"),
+            detected_mime: "image/jpeg",
+            current_ext: "txt".to_string(),
+            suggested_ext: "jpg",
+        };
+
+        let script = fix_script(&[finding]);
+        // The metacharacters are still present verbatim — that's correct
+        // and expected: single-quoting renders them inert rather than
+        // stripping them, so what matters is that they end up *inside* the
+        // quotes rather than breaking out of them.
+        assert!(script.contains(r"'$(touch /tmp/mime_to_ext_pwned).txt'"));
+    }
+
+    #[test]
+    fn shell_quote_escapes_embedded_single_quotes() {
+        assert_eq!(shell_quote("it's a test"), r"'it'\''s a test'");
+    }
+
+    #[test]
+    fn ignore_unknown_exts_suppresses_findings_for_unlisted_extensions() {
+        let dir = temp_dir("mime_to_ext_scan_test_unknown");
+        let file = dir.join("photo.totallyunknownext");
+        fs::File::create(&file)
+            .unwrap()
+            .write_all(&[0xFF, 0xD8, 0xFF, 0x00])
+            .unwrap();
+
+        let options = ScanOptions {
+            ignore_unknown_exts: true,
+        };
+        let findings = scan(&dir, options).unwrap();
+        assert!(findings.is_empty());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,112 @@
+//! Content-based MIME sniffing from magic bytes.
+//!
+//! Unlike [`crate::mime_to_ext`] / [`crate::ext_to_mime`], which trust a
+//! caller-supplied string, this module inspects the actual bytes of a file
+//! to determine its type. Useful for verifying that a file's real type
+//! matches what its extension claims.
+
+#[cfg(feature = "std")]
+extern crate std;
+
+/// Number of leading bytes inspected when sniffing content. Signatures in
+/// [`SIGNATURES`] never reach past this, so callers can read just this many
+/// bytes from a stream instead of buffering the whole file.
+pub const BUF_SIZE: usize = 8 * 1024;
+
+/// A single magic-byte signature: match `magic` against `buf[offset..]`.
+type Signature = (usize, &'static [u8], &'static str);
+
+/// Well-known file signatures, as used by common file-identification tools.
+static SIGNATURES: &[Signature] = &[
+    (0, &[0xFF, 0xD8, 0xFF], "image/jpeg"),
+    (
+        0,
+        &[0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A],
+        "image/png",
+    ),
+    (0, b"%PDF-", "application/pdf"),
+    (0, b"PK\x03\x04", "application/zip"),
+    (0, b"GIF8", "image/gif"),
+];
+
+/// Identify a MIME type by inspecting the content of `buf`.
+///
+/// Only the first [`BUF_SIZE`] bytes matter; passing more is harmless but
+/// wasteful for streaming callers. Returns `None` if no known signature
+/// matches.
+///
+/// # Example
+/// ```
+/// # use mime_to_ext::sniff::detect_from_bytes;
+/// assert_eq!(detect_from_bytes(b"%PDF-1.4"), Some("application/pdf"));
+/// assert_eq!(detect_from_bytes(b"not a file"), None);
+/// ```
+pub fn detect_from_bytes(buf: &[u8]) -> Option<&'static str> {
+    let buf = if buf.len() > BUF_SIZE {
+        &buf[..BUF_SIZE]
+    } else {
+        buf
+    };
+    SIGNATURES.iter().find_map(|&(offset, magic, mime)| {
+        let end = offset.checked_add(magic.len())?;
+        if end > buf.len() {
+            return None;
+        }
+        if &buf[offset..end] == magic {
+            Some(mime)
+        } else {
+            None
+        }
+    })
+}
+
+/// Identify a MIME type by reading the first [`BUF_SIZE`] bytes of the file
+/// at `path`.
+///
+/// Requires the `std` feature, since it performs actual file I/O.
+#[cfg(feature = "std")]
+pub fn detect_from_path<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Option<&'static str>> {
+    use std::io::Read;
+
+    let mut file = std::fs::File::open(path)?;
+    let mut buf = [0u8; BUF_SIZE];
+    let mut len = 0;
+    while len < buf.len() {
+        match file.read(&mut buf[len..])? {
+            0 => break,
+            n => len += n,
+        }
+    }
+    Ok(detect_from_bytes(&buf[..len]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_known_signatures() {
+        assert_eq!(
+            detect_from_bytes(&[0xFF, 0xD8, 0xFF, 0x00]),
+            Some("image/jpeg")
+        );
+        assert_eq!(
+            detect_from_bytes(&[0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A]),
+            Some("image/png")
+        );
+        assert_eq!(detect_from_bytes(b"%PDF-1.7 rest"), Some("application/pdf"));
+        assert_eq!(detect_from_bytes(b"PK\x03\x04 zip"), Some("application/zip"));
+        assert_eq!(detect_from_bytes(b"GIF89a"), Some("image/gif"));
+    }
+
+    #[test]
+    fn unknown_content_gives_none() {
+        assert_eq!(detect_from_bytes(b"plain text"), None);
+        assert_eq!(detect_from_bytes(&[]), None);
+    }
+
+    #[test]
+    fn short_buffer_does_not_panic() {
+        assert_eq!(detect_from_bytes(&[0xFF]), None);
+    }
+}